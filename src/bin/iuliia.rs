@@ -0,0 +1,110 @@
+//! CLI for piping text through an `iuliia_rust` schema.
+//!
+//! ```text
+//! iuliia --schema wikipedia "Юлия"
+//! echo "Юлия" | iuliia --schema wikipedia
+//! iuliia --list
+//! ```
+
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+use iuliia_rust::{BuiltinSchema, Schema, parse_by_schema, parse_reverse_by_schema};
+
+struct Args {
+    schema: Option<String>,
+    reverse: bool,
+    list: bool,
+    text: Vec<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        schema: None,
+        reverse: false,
+        list: false,
+        text: Vec::new(),
+    };
+    let mut raw = std::env::args().skip(1);
+    while let Some(arg) = raw.next() {
+        match arg.as_str() {
+            "--schema" => {
+                args.schema = Some(raw.next().ok_or("--schema requires a value")?);
+            }
+            "--reverse" => args.reverse = true,
+            "--list" => args.list = true,
+            text => args.text.push(text.to_owned()),
+        }
+    }
+    Ok(args)
+}
+
+fn print_usage() {
+    eprintln!(
+        "Usage: iuliia --schema <name> [--reverse] [TEXT...]\n       iuliia --list\n\nReads TEXT from the positional arguments, or line-by-line from stdin when none are given."
+    );
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {err}");
+            print_usage();
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.list {
+        for schema in BuiltinSchema::ALL {
+            println!("{}", schema.name());
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let Some(schema_name) = args.schema else {
+        eprintln!("error: --schema <name> is required");
+        print_usage();
+        return ExitCode::FAILURE;
+    };
+    let schema = match Schema::try_for_name(&schema_name) {
+        Ok(schema) => schema,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let translate = |line: &str| -> String {
+        if args.reverse {
+            parse_reverse_by_schema(line, schema).primary
+        } else {
+            parse_by_schema(line, schema)
+        }
+    };
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    if args.text.is_empty() {
+        for line in io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if writeln!(stdout, "{}", translate(&line)).is_err() {
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        let text = args.text.join(" ");
+        if writeln!(stdout, "{}", translate(&text)).is_err() {
+            return ExitCode::FAILURE;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -3,80 +3,21 @@
 extern crate include_dir;
 extern crate regex;
 
-use include_dir::Dir;
 use regex::Regex;
 
 pub use serde::Deserialize;
-use std::collections::HashMap;
 use std::iter::once;
 use std::sync::LazyLock;
 
-const SCHEMA_DIR: Dir = include_dir!("./iuliia");
-const DUMMY_SYMBOL: &str = "$";
+mod reverse;
+mod schema;
+mod slug;
 
-/// Describe struct of transliterate schema
-#[derive(Deserialize, Debug)]
-pub struct Schema {
-    #[cfg(test)]
-    name: String,
-    #[cfg(test)]
-    description: String,
-    #[cfg(test)]
-    url: String,
-    mapping: Option<HashMap<String, String>>,
-    prev_mapping: Option<HashMap<String, String>>,
-    next_mapping: Option<HashMap<String, String>>,
-    ending_mapping: Option<HashMap<String, String>>,
-    #[cfg(test)]
-    samples: Option<Vec<Vec<String>>>,
-}
+pub use reverse::{ReverseMatch, parse_reverse_by_schema};
+pub use schema::{BuiltinSchema, Schema, SchemaBuilder, SchemaError, Translate};
+pub use slug::{SlugOptions, slugify};
 
-impl Schema {
-    /// Get a schema object by schema name
-    ///
-    /// # Panics
-    //  - If schema not found
-    #[must_use]
-    pub fn for_name(schema: &str) -> Schema {
-        let schema_file = SCHEMA_DIR
-            .get_file(format!("{schema}.json"))
-            .unwrap_or_else(|| panic!("There are no schema with name {schema}"));
-        serde_json::from_str(schema_file.contents_utf8().expect("contents_utf8() failed"))
-            .expect("Schema deserialization error")
-    }
-
-    #[must_use]
-    pub fn get_pref(&self, s: &str) -> Option<&str> {
-        self.prev_mapping
-            .as_ref()?
-            .get(&s.replace(DUMMY_SYMBOL, "").to_lowercase())
-            .map(String::as_str)
-    }
-
-    #[must_use]
-    pub fn get_next(&self, s: &str) -> Option<&str> {
-        self.next_mapping
-            .as_ref()?
-            .get(&s.replace(DUMMY_SYMBOL, "").to_lowercase())
-            .map(String::as_str)
-    }
-
-    #[must_use]
-    pub fn get_letter(&self, s: &str) -> Option<&str> {
-        self.mapping
-            .as_ref()?
-            .get(&s.replace(DUMMY_SYMBOL, "").to_lowercase())
-            .map(String::as_str)
-    }
-
-    #[must_use]
-    pub fn get_ending(&self, s: &str) -> Option<&str> {
-        self.ending_mapping
-            .as_ref()?
-            .get(&s.to_lowercase())
-            .map(String::as_str)
-    }
-}
+const DUMMY_SYMBOL: &str = "$";
 
 /// Transliterate a slice of str using name of schema to `String`
 ///
@@ -86,7 +27,7 @@ impl Schema {
 ///
 #[must_use]
 pub fn parse_by_schema_name(s: &str, schema_name: &str) -> String {
-    parse_by_schema(s, &Schema::for_name(schema_name))
+    parse_by_schema(s, Schema::for_name(schema_name))
 }
 
 /// Transliterate a slice of str using `Schema` to `String`
@@ -97,7 +38,7 @@ pub fn parse_by_schema_name(s: &str, schema_name: &str) -> String {
 /// let expected = "Yuliya, syesh yeshchyo etikh myagkikh frantsuzskikh bulok iz Yoshkar-Oly, da vypey altayskogo chayu";
 /// let schema = iuliia_rust::Schema::for_name("wikipedia");
 ///
-/// let transliterated_word = iuliia_rust::parse_by_schema(&input, &schema);
+/// let transliterated_word = iuliia_rust::parse_by_schema(&input, schema);
 ///
 /// assert_eq!(transliterated_word, expected)
 /// ```
@@ -124,16 +65,61 @@ pub fn parse_word_by_schema(s: &str, schema: &Schema) -> String {
         (String::new(), word_by_letters)
     };
 
-    //Add dummy symbols for window function
-    //Parse each letter
-    once(DUMMY_SYMBOL.into())
+    //Add dummy symbols for longest-match scanner
+    let tokens: Vec<String> = once(DUMMY_SYMBOL.into())
         .chain(word_without_ending)
         .chain(once(DUMMY_SYMBOL.into()))
-        .collect::<Vec<_>>()
-        .windows(3)
-        .map(|letter_with_neighbors| parse_letter(letter_with_neighbors, schema))
-        .chain(once(parsed_end))
-        .collect::<String>()
+        .collect();
+    let candidate_lengths = schema.candidate_source_lengths();
+    let last_real = tokens.len() - 2;
+
+    let mut result = String::new();
+    let mut cursor = 1;
+    while cursor <= last_real {
+        let (translated, consumed) =
+            parse_rule(&tokens, cursor, last_real, candidate_lengths, schema);
+        result.push_str(&translated);
+        cursor += consumed;
+    }
+    result.push_str(&parsed_end);
+    result
+}
+
+/// Try every candidate source length at `cursor`, longest first, matching a
+/// prefix rule, then a postfix rule, then a plain letter rule at that
+/// length. Falls through to the identity mapping of the current char when
+/// nothing matches.
+///
+/// Returns the translated text and how many source chars it consumed.
+fn parse_rule(
+    tokens: &[String],
+    cursor: usize,
+    last_real: usize,
+    candidate_lengths: &[usize],
+    schema: &Schema,
+) -> (String, usize) {
+    for &len in candidate_lengths {
+        if cursor + len - 1 > last_real {
+            continue;
+        }
+        let source: String = tokens[cursor..cursor + len].concat();
+        let source_lower = source.to_lowercase();
+        let left = &tokens[cursor - 1];
+        let right = &tokens[cursor + len];
+
+        let matched = schema
+            .get_pref(&format!("{left}{source_lower}"))
+            .or_else(|| schema.get_next(&format!("{source_lower}{right}")))
+            .or_else(|| schema.get_letter(&source_lower));
+
+        if let Some(matched) = matched {
+            return (propagate_case_from_source(matched, &source, true), len);
+        }
+    }
+
+    // Identity fallback: no rule matched at any length, keep the char as-is.
+    let letter = tokens[cursor].clone();
+    (letter, 1)
 }
 
 fn parse_ending(s: &[String], schema: &Schema) -> Option<Ending> {
@@ -160,25 +146,7 @@ struct Ending {
     ending_start: usize,
 }
 
-/// Find letter transliteration with steps priority(apply higher):
-/// 1. prefix parse
-/// 2. postfix parse
-/// 3. letter parse
-/// 4. use input letter
-fn parse_letter(letter_with_neighbors: &[String], schema: &Schema) -> String {
-    let letter: String = letter_with_neighbors[1..2].concat();
-    propagate_case_from_source(
-        schema
-            .get_pref(&letter_with_neighbors[..2].concat())
-            .or_else(|| schema.get_next(&letter_with_neighbors[1..].concat()))
-            .or_else(|| schema.get_letter(&letter))
-            .unwrap_or(&letter),
-        &letter,
-        true,
-    )
-}
-
-fn propagate_case_from_source(
+pub(crate) fn propagate_case_from_source(
     result: &str,
     source_letter: &str,
     only_first_symbol: bool,
@@ -200,19 +168,20 @@ fn propagate_case_from_source(
 
 #[cfg(test)]
 mod tests {
-    use crate::{Schema, parse_by_schema};
+    use crate::{Schema, SchemaBuilder, parse_by_schema};
+    use std::collections::HashMap;
 
     #[test]
     fn schema_test() {
         let schema = Schema::for_name("ala_lc");
-        assert_eq!(schema.name, "ala_lc");
+        assert_eq!(schema.name(), "ala_lc");
     }
 
     #[test]
     fn simple_word_test() {
         let schema = Schema::for_name("wikipedia");
         for (original, expected) in [("б", "b"), ("пол", "pol")] {
-            assert_eq!(parse_by_schema(original, &schema), expected);
+            assert_eq!(parse_by_schema(original, schema), expected);
         }
     }
 
@@ -220,7 +189,7 @@ mod tests {
     fn prefix_word_test() {
         let schema = Schema::for_name("wikipedia");
         for (original, expected) in [("ель", "yel")] {
-            assert_eq!(parse_by_schema(original, &schema), expected);
+            assert_eq!(parse_by_schema(original, schema), expected);
         }
     }
 
@@ -228,7 +197,7 @@ mod tests {
     fn postfix_word_test() {
         let schema = Schema::for_name("wikipedia");
         for (original, expected) in [("бульон", "bulyon")] {
-            assert_eq!(parse_by_schema(original, &schema), expected);
+            assert_eq!(parse_by_schema(original, schema), expected);
         }
     }
 
@@ -236,7 +205,7 @@ mod tests {
     fn test_letter_case() {
         let schema = Schema::for_name("wikipedia");
         for (original, expected) in [("ноГа", "noGa"), ("Рука", "Ruka")] {
-            assert_eq!(parse_by_schema(original, &schema), expected);
+            assert_eq!(parse_by_schema(original, schema), expected);
         }
     }
 
@@ -244,7 +213,7 @@ mod tests {
     fn test_ending() {
         let schema = Schema::for_name("wikipedia");
         for (original, expected) in [("хороший", "khoroshy")] {
-            assert_eq!(parse_by_schema(original, &schema), expected);
+            assert_eq!(parse_by_schema(original, schema), expected);
         }
     }
 
@@ -258,7 +227,34 @@ mod tests {
             ),
             ("ВЕЛИКИЙ", "VELIKY"),
         ] {
-            assert_eq!(parse_by_schema(original, &schema), expected);
+            assert_eq!(parse_by_schema(original, schema), expected);
         }
     }
+
+    #[test]
+    fn longest_candidate_length_wins_over_single_letters() {
+        let schema = SchemaBuilder::new()
+            .mapping(HashMap::from([
+                ("к".to_owned(), "k".to_owned()),
+                ("с".to_owned(), "s".to_owned()),
+                ("кс".to_owned(), "x".to_owned()),
+            ]))
+            .build();
+        // "к" + "с" would otherwise greedily translate as "ks".
+        assert_eq!(parse_by_schema("кс", &schema), "x");
+    }
+
+    #[test]
+    fn prev_mapping_rule_wins_over_plain_letter_at_the_same_position() {
+        let schema = SchemaBuilder::new()
+            .mapping(HashMap::from([
+                ("о".to_owned(), "o".to_owned()),
+                ("а".to_owned(), "a".to_owned()),
+            ]))
+            .prev_mapping(HashMap::from([("оа".to_owned(), "A".to_owned())]))
+            .build();
+        // The "а" after "о" takes the prev_mapping rule instead of its own
+        // plain mapping entry.
+        assert_eq!(parse_by_schema("оа", &schema), "oA");
+    }
 }
@@ -0,0 +1,252 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::{Schema, propagate_case_from_source};
+
+/// Result of reversing one piece of Latin text back to Cyrillic.
+///
+/// Reversal is inherently ambiguous (`e` could be `е`/`э`; `y` could be
+/// `ы`/`й`), so alongside the best-effort [`ReverseMatch::primary`]
+/// reconstruction, [`ReverseMatch::alternates`] holds one variant per
+/// runner-up candidate of each ambiguous span, with that candidate
+/// substituted in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReverseMatch {
+    /// The best-effort Cyrillic reconstruction.
+    pub primary: String,
+    /// Alternate reconstructions, each differing from `primary` at exactly
+    /// one ambiguous span.
+    pub alternates: Vec<String>,
+}
+
+/// A Latin source string mapped back to its candidate Cyrillic sources,
+/// longest Latin source first so `shch` resolves to `щ` before `sh` can
+/// claim `ш` out of it.
+#[derive(Debug)]
+pub(crate) struct ReverseIndex {
+    candidates: HashMap<String, Vec<String>>,
+    lengths: Vec<usize>,
+}
+
+impl ReverseIndex {
+    pub(crate) fn build(schema: &Schema) -> ReverseIndex {
+        let mut candidates: HashMap<String, Vec<String>> = HashMap::new();
+        let mut insert = |latin: &str, source: &str| {
+            // An empty Latin output (e.g. a dropped soft sign) cannot be
+            // located in the input, so it is not indexed.
+            if latin.is_empty() {
+                return;
+            }
+            let entry = candidates.entry(latin.to_lowercase()).or_default();
+            let source = source.to_lowercase();
+            if !entry.contains(&source) {
+                entry.push(source);
+            }
+        };
+        for table in [&schema.mapping, &schema.ending_mapping] {
+            let Some(table) = table else { continue };
+            for (cyrillic, latin) in table {
+                insert(latin, cyrillic);
+            }
+        }
+        // `prev_mapping`/`next_mapping` keys bundle one context char onto the
+        // source (see `Schema::candidate_source_lengths`), so it has to be
+        // stripped back off before the rest of the key can stand in for the
+        // recovered Cyrillic source on its own. A one-char key has no context
+        // char (it is a word-boundary rule) and is kept whole.
+        if let Some(table) = &schema.prev_mapping {
+            for (cyrillic, latin) in table {
+                insert(latin, strip_leading_char(cyrillic));
+            }
+        }
+        if let Some(table) = &schema.next_mapping {
+            for (cyrillic, latin) in table {
+                insert(latin, strip_trailing_char(cyrillic));
+            }
+        }
+        for variants in candidates.values_mut() {
+            variants.sort_unstable();
+        }
+        let mut lengths: Vec<usize> = candidates.keys().map(|key| key.chars().count()).collect();
+        lengths.push(1);
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+        lengths.dedup();
+        ReverseIndex { candidates, lengths }
+    }
+}
+
+fn strip_leading_char(s: &str) -> &str {
+    let mut chars = s.chars();
+    chars.next();
+    if chars.as_str().is_empty() {
+        s
+    } else {
+        chars.as_str()
+    }
+}
+
+fn strip_trailing_char(s: &str) -> &str {
+    let mut chars = s.chars();
+    chars.next_back();
+    if chars.as_str().is_empty() {
+        s
+    } else {
+        chars.as_str()
+    }
+}
+
+/// Invert a schema's mapping tables to recover Cyrillic from Latin text.
+///
+/// Greedily scans `s` trying the longest known Latin source at each
+/// position first, falling back to passing the char through unchanged when
+/// no table entry matches it.
+#[must_use]
+pub fn parse_reverse_by_schema(s: &str, schema: &Schema) -> ReverseMatch {
+    static RE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\b").expect("Failed to compile regex"));
+    let index = schema.reverse_index();
+
+    let words: Vec<(String, Vec<String>)> = RE
+        .split(s)
+        .map(|word| parse_reverse_word(word, index))
+        .collect();
+
+    let primary: String = words.iter().map(|(word, _)| word.as_str()).collect();
+    let alternates = words
+        .iter()
+        .enumerate()
+        .flat_map(|(i, (_, word_alternates))| {
+            word_alternates
+                .iter()
+                .map(|alternate| {
+                    words[..i]
+                        .iter()
+                        .map(|(word, _)| word.as_str())
+                        .chain(std::iter::once(alternate.as_str()))
+                        .chain(words[i + 1..].iter().map(|(word, _)| word.as_str()))
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    ReverseMatch {
+        primary,
+        alternates,
+    }
+}
+
+fn parse_reverse_word(s: &str, index: &ReverseIndex) -> (String, Vec<String>) {
+    let tokens: Vec<String> = s.chars().map(|char| char.to_string()).collect();
+    let mut primary = String::new();
+    // One entry per ambiguous slot: (byte span in `primary`, runner-up texts).
+    let mut ambiguous_spans: Vec<(std::ops::Range<usize>, Vec<String>)> = Vec::new();
+    let mut cursor = 0;
+    while cursor < tokens.len() {
+        let (chosen, rest, consumed) = best_candidates(&tokens, cursor, index);
+        if !rest.is_empty() {
+            let span = primary.len()..primary.len() + chosen.len();
+            ambiguous_spans.push((span, rest));
+        }
+        primary.push_str(&chosen);
+        cursor += consumed;
+    }
+
+    let alternates = ambiguous_spans
+        .into_iter()
+        .flat_map(|(span, rest)| {
+            rest.into_iter()
+                .map(|candidate| {
+                    let mut alternate = primary.clone();
+                    alternate.replace_range(span.clone(), &candidate);
+                    alternate
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    (primary, alternates)
+}
+
+/// The chosen candidate, any runner-up candidates, and how many source
+/// chars were consumed, for the longest matching Latin span at `cursor`.
+fn best_candidates(
+    tokens: &[String],
+    cursor: usize,
+    index: &ReverseIndex,
+) -> (String, Vec<String>, usize) {
+    for &len in &index.lengths {
+        if cursor + len > tokens.len() {
+            continue;
+        }
+        let source: String = tokens[cursor..cursor + len].concat();
+        if let Some(candidates) = index.candidates.get(&source.to_lowercase()) {
+            let chosen = propagate_case_from_source(&candidates[0], &source, true);
+            let rest = candidates[1..]
+                .iter()
+                .map(|candidate| propagate_case_from_source(candidate, &source, true))
+                .collect();
+            return (chosen, rest, len);
+        }
+    }
+
+    (tokens[cursor].clone(), Vec::new(), 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_reverse_by_schema;
+    use crate::SchemaBuilder;
+    use std::collections::HashMap;
+
+    fn test_schema() -> crate::Schema {
+        SchemaBuilder::new()
+            .mapping(HashMap::from([
+                ("ш".to_owned(), "sh".to_owned()),
+                ("щ".to_owned(), "shch".to_owned()),
+                ("ч".to_owned(), "ch".to_owned()),
+                ("е".to_owned(), "e".to_owned()),
+                ("э".to_owned(), "e".to_owned()),
+            ]))
+            .build()
+    }
+
+    #[test]
+    fn longest_match_wins_over_shorter_prefix() {
+        let schema = test_schema();
+        // "sh" + "ch" would otherwise greedily read as ш + ч.
+        assert_eq!(parse_reverse_by_schema("shch", &schema).primary, "щ");
+    }
+
+    #[test]
+    fn ambiguous_source_exposes_primary_and_alternate() {
+        let schema = test_schema();
+        let result = parse_reverse_by_schema("e", &schema);
+        assert_eq!(result.primary, "е");
+        assert_eq!(result.alternates, vec!["э"]);
+    }
+
+    #[test]
+    fn case_is_propagated_to_primary_and_alternates() {
+        let schema = test_schema();
+        let result = parse_reverse_by_schema("E", &schema);
+        assert_eq!(result.primary, "Е");
+        assert_eq!(result.alternates, vec!["Э"]);
+    }
+
+    #[test]
+    fn prev_mapping_candidate_excludes_the_context_char() {
+        let schema = SchemaBuilder::new()
+            .mapping(HashMap::from([
+                ("с".to_owned(), "s".to_owned()),
+                ("м".to_owned(), "m".to_owned()),
+            ]))
+            .prev_mapping(HashMap::from([("са".to_owned(), "ya".to_owned())]))
+            .build();
+        // Forward: "сам" -> "s" + "yam" ("а" after "с" uses prev_mapping).
+        assert_eq!(crate::parse_by_schema("сам", &schema), "syam");
+        // The "ya" candidate should recover just the "а" it replaced, not
+        // "са" (which would double up the "с" already carried by "s").
+        assert_eq!(parse_reverse_by_schema("syam", &schema).primary, "сам");
+    }
+}
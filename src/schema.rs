@@ -0,0 +1,509 @@
+use include_dir::Dir;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+use std::sync::{LazyLock, OnceLock};
+
+use crate::DUMMY_SYMBOL;
+use crate::reverse::ReverseIndex;
+
+const SCHEMA_DIR: Dir = include_dir!("./iuliia");
+
+/// Describe struct of transliterate schema
+#[derive(Deserialize, Debug, Default)]
+pub struct Schema {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) url: String,
+    pub(crate) mapping: Option<HashMap<String, String>>,
+    pub(crate) prev_mapping: Option<HashMap<String, String>>,
+    pub(crate) next_mapping: Option<HashMap<String, String>>,
+    pub(crate) ending_mapping: Option<HashMap<String, String>>,
+    pub(crate) samples: Option<Vec<Vec<String>>>,
+    /// Lazily built, then cached for the lifetime of this `Schema`.
+    #[serde(skip)]
+    reverse_index: OnceLock<ReverseIndex>,
+    /// Lazily computed, then cached for the lifetime of this `Schema`.
+    #[serde(skip)]
+    candidate_source_lengths: OnceLock<Vec<usize>>,
+}
+
+/// Error returned when a schema cannot be loaded or fails its self-test.
+#[derive(Debug)]
+pub enum SchemaError {
+    /// No bundled schema file matches the requested name.
+    NotFound(String),
+    /// The schema file exists but could not be parsed as JSON.
+    Invalid(String, String),
+    /// A schema's JSON could not be parsed (no file name to report).
+    Parse(serde_json::Error),
+    /// A schema's JSON could not be read from its source.
+    Io(std::io::Error),
+    /// One of `samples` transliterated to something other than expected.
+    SampleMismatch {
+        input: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::NotFound(name) => write!(f, "there is no schema with name {name}"),
+            SchemaError::Invalid(name, err) => {
+                write!(f, "schema {name} failed to deserialize: {err}")
+            }
+            SchemaError::Parse(err) => write!(f, "schema failed to deserialize: {err}"),
+            SchemaError::Io(err) => write!(f, "schema could not be read: {err}"),
+            SchemaError::SampleMismatch {
+                input,
+                expected,
+                actual,
+            } => write!(f, "sample {input:?} expected {expected:?}, got {actual:?}"),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SchemaError::NotFound(_)
+            | SchemaError::Invalid(..)
+            | SchemaError::SampleMismatch { .. } => None,
+            SchemaError::Parse(err) => Some(err),
+            SchemaError::Io(err) => Some(err),
+        }
+    }
+}
+
+/// Bundled schemas, keyed by name. A file that fails to parse (bad JSON or
+/// non-UTF8 contents) is kept as its rendered error rather than panicking,
+/// so a single corrupt file only fails lookups for *its own* name instead of
+/// taking every other bundled schema down with it.
+static REGISTRY: LazyLock<HashMap<&'static str, Result<Schema, String>>> = LazyLock::new(|| {
+    SCHEMA_DIR
+        .files()
+        .map(|file| {
+            let name = file
+                .path()
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_else(|| {
+                    panic!(
+                        "bundled schema file {} has a non-UTF8 name",
+                        file.path().display()
+                    )
+                });
+            let schema = serde_json::from_slice::<Schema>(file.contents())
+                .map_err(|err| err.to_string());
+            (name, schema)
+        })
+        .collect()
+});
+
+impl Schema {
+    /// Get a schema object by schema name
+    ///
+    /// # Panics
+    //  - If schema not found
+    #[must_use]
+    pub fn for_name(schema: &str) -> &'static Schema {
+        Self::try_for_name(schema).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Get a schema object by schema name, without panicking.
+    ///
+    /// # Errors
+    /// - [`SchemaError::NotFound`] if no bundled schema matches `schema`.
+    /// - [`SchemaError::Invalid`] if the bundled file for `schema` failed to
+    ///   parse. Other bundled schemas are unaffected.
+    pub fn try_for_name(schema: &str) -> Result<&'static Schema, SchemaError> {
+        match REGISTRY.get(schema) {
+            Some(Ok(schema)) => Ok(schema),
+            Some(Err(err)) => Err(SchemaError::Invalid(schema.to_owned(), err.clone())),
+            None => Err(SchemaError::NotFound(schema.to_owned())),
+        }
+    }
+
+    #[must_use]
+    pub fn get_pref(&self, s: &str) -> Option<&str> {
+        self.prev_mapping
+            .as_ref()?
+            .get(&s.replace(DUMMY_SYMBOL, "").to_lowercase())
+            .map(String::as_str)
+    }
+
+    #[must_use]
+    pub fn get_next(&self, s: &str) -> Option<&str> {
+        self.next_mapping
+            .as_ref()?
+            .get(&s.replace(DUMMY_SYMBOL, "").to_lowercase())
+            .map(String::as_str)
+    }
+
+    #[must_use]
+    pub fn get_letter(&self, s: &str) -> Option<&str> {
+        self.mapping
+            .as_ref()?
+            .get(&s.replace(DUMMY_SYMBOL, "").to_lowercase())
+            .map(String::as_str)
+    }
+
+    #[must_use]
+    pub fn get_ending(&self, s: &str) -> Option<&str> {
+        self.ending_mapping
+            .as_ref()?
+            .get(&s.to_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Parse a schema from a JSON string, e.g. one loaded from disk or a
+    /// config file rather than bundled at compile time.
+    ///
+    /// # Errors
+    /// - [`SchemaError::Parse`] if `s` is not a valid schema document.
+    pub fn from_json_str(s: &str) -> Result<Schema, SchemaError> {
+        serde_json::from_str(s).map_err(SchemaError::Parse)
+    }
+
+    /// Parse a schema by reading JSON from `reader` to completion.
+    ///
+    /// # Errors
+    /// - [`SchemaError::Io`] if `reader` could not be read.
+    /// - [`SchemaError::Parse`] if the contents are not a valid schema
+    ///   document.
+    pub fn from_reader(mut reader: impl Read) -> Result<Schema, SchemaError> {
+        let mut contents = String::new();
+        reader
+            .read_to_string(&mut contents)
+            .map_err(SchemaError::Io)?;
+        Self::from_json_str(&contents)
+    }
+
+    /// The schema's short identifier, e.g. `"wikipedia"`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A human-readable description of the schema.
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// A URL with more information about the schema.
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// `[input, expected]` pairs used by [`Schema::self_test`].
+    #[must_use]
+    pub fn samples(&self) -> &[Vec<String>] {
+        self.samples.as_deref().unwrap_or_default()
+    }
+
+    /// Re-run the schema's bundled `[input, expected]` samples against its
+    /// own rule tables, e.g. after loading it from disk to confirm it
+    /// transliterates the way its author intended.
+    ///
+    /// # Errors
+    /// - [`SchemaError::SampleMismatch`] on the first sample whose
+    ///   transliteration doesn't match its expected output.
+    pub fn self_test(&self) -> Result<(), SchemaError> {
+        for sample in self.samples() {
+            let (Some(input), Some(expected)) = (sample.first(), sample.get(1)) else {
+                continue;
+            };
+            let actual = crate::parse_by_schema(input, self);
+            if &actual != expected {
+                return Err(SchemaError::SampleMismatch {
+                    input: input.clone(),
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The schema's inverted (Latin-to-Cyrillic) rule table, built on first
+    /// use and cached for the lifetime of this `Schema`.
+    pub(crate) fn reverse_index(&self) -> &ReverseIndex {
+        self.reverse_index.get_or_init(|| ReverseIndex::build(self))
+    }
+
+    /// Every distinct source length (in chars) a rule table in this schema
+    /// could match, longest first, always including `1` for the
+    /// single-letter/identity fallback. Computed on first use and cached
+    /// for the lifetime of this `Schema`.
+    ///
+    /// `prev_mapping`/`next_mapping` keys bundle one context char onto the
+    /// source, so their contribution is their length minus one.
+    pub(crate) fn candidate_source_lengths(&self) -> &[usize] {
+        self.candidate_source_lengths
+            .get_or_init(|| self.compute_candidate_source_lengths())
+    }
+
+    fn compute_candidate_source_lengths(&self) -> Vec<usize> {
+        let mut lengths: Vec<usize> = [&self.mapping, &self.prev_mapping, &self.next_mapping]
+            .into_iter()
+            .zip([0, 1, 1])
+            .flat_map(|(table, context_len)| {
+                table.iter().flat_map(move |table| {
+                    table
+                        .keys()
+                        .map(move |key| key.chars().count().saturating_sub(context_len).max(1))
+                })
+            })
+            .collect();
+        lengths.push(1);
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+        lengths.dedup();
+        lengths
+    }
+}
+
+/// Compile-time handle for every schema bundled under `iuliia/`.
+///
+/// Each variant resolves to its backing [`Schema`] by looking up its fixed
+/// file name in the bundled registry, so callers cannot pass a misspelled
+/// name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltinSchema {
+    AlaLc,
+    BgnPcgn,
+    Bs2979,
+    Gost16876,
+    Gost52290,
+    Gost52535,
+    Gost7034,
+    IcoDoc9303,
+    MosMetro,
+    Mvd310,
+    Mvd782,
+    Scientific,
+    Telegram,
+    Ungegn1987,
+    Wikipedia,
+    YandexMaps,
+    YandexMoney,
+}
+
+/// Something that can transliterate text by way of a backing [`Schema`].
+pub trait Translate {
+    /// Transliterate `s` using this schema.
+    fn transliterate(&self, s: &str) -> String;
+}
+
+impl Translate for Schema {
+    fn transliterate(&self, s: &str) -> String {
+        crate::parse_by_schema(s, self)
+    }
+}
+
+impl Translate for BuiltinSchema {
+    fn transliterate(&self, s: &str) -> String {
+        crate::parse_by_schema(s, self.schema())
+    }
+}
+
+impl BuiltinSchema {
+    /// Every bundled schema, in declaration order.
+    pub const ALL: [BuiltinSchema; 17] = [
+        BuiltinSchema::AlaLc,
+        BuiltinSchema::BgnPcgn,
+        BuiltinSchema::Bs2979,
+        BuiltinSchema::Gost16876,
+        BuiltinSchema::Gost52290,
+        BuiltinSchema::Gost52535,
+        BuiltinSchema::Gost7034,
+        BuiltinSchema::IcoDoc9303,
+        BuiltinSchema::MosMetro,
+        BuiltinSchema::Mvd310,
+        BuiltinSchema::Mvd782,
+        BuiltinSchema::Scientific,
+        BuiltinSchema::Telegram,
+        BuiltinSchema::Ungegn1987,
+        BuiltinSchema::Wikipedia,
+        BuiltinSchema::YandexMaps,
+        BuiltinSchema::YandexMoney,
+    ];
+
+    /// The bundled schema file name backing this variant.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            BuiltinSchema::AlaLc => "ala_lc",
+            BuiltinSchema::BgnPcgn => "bgn_pcgn",
+            BuiltinSchema::Bs2979 => "bs_2979",
+            BuiltinSchema::Gost16876 => "gost_16876",
+            BuiltinSchema::Gost52290 => "gost_52290",
+            BuiltinSchema::Gost52535 => "gost_52535",
+            BuiltinSchema::Gost7034 => "gost_7034",
+            BuiltinSchema::IcoDoc9303 => "ico_doc_9303",
+            BuiltinSchema::MosMetro => "mosmetro",
+            BuiltinSchema::Mvd310 => "mvd_310",
+            BuiltinSchema::Mvd782 => "mvd_782",
+            BuiltinSchema::Scientific => "scientific",
+            BuiltinSchema::Telegram => "telegram",
+            BuiltinSchema::Ungegn1987 => "ungegn_1987",
+            BuiltinSchema::Wikipedia => "wikipedia",
+            BuiltinSchema::YandexMaps => "yandex_maps",
+            BuiltinSchema::YandexMoney => "yandex_money",
+        }
+    }
+
+    /// The [`Schema`] backing this variant.
+    ///
+    /// # Panics
+    /// - If the bundled schema file is missing or fails to deserialize, which
+    ///   would indicate a packaging bug rather than user error.
+    #[must_use]
+    pub fn schema(self) -> &'static Schema {
+        Schema::for_name(self.name())
+    }
+}
+
+/// Build a [`Schema`] programmatically, for custom or regional transcription
+/// variants that don't ship as a bundled JSON file.
+#[derive(Debug, Default)]
+pub struct SchemaBuilder {
+    schema: Schema,
+}
+
+impl SchemaBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.schema.name = name.into();
+        self
+    }
+
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.schema.description = description.into();
+        self
+    }
+
+    #[must_use]
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.schema.url = url.into();
+        self
+    }
+
+    #[must_use]
+    pub fn mapping(mut self, mapping: HashMap<String, String>) -> Self {
+        self.schema.mapping = Some(mapping);
+        self
+    }
+
+    #[must_use]
+    pub fn prev_mapping(mut self, prev_mapping: HashMap<String, String>) -> Self {
+        self.schema.prev_mapping = Some(prev_mapping);
+        self
+    }
+
+    #[must_use]
+    pub fn next_mapping(mut self, next_mapping: HashMap<String, String>) -> Self {
+        self.schema.next_mapping = Some(next_mapping);
+        self
+    }
+
+    #[must_use]
+    pub fn ending_mapping(mut self, ending_mapping: HashMap<String, String>) -> Self {
+        self.schema.ending_mapping = Some(ending_mapping);
+        self
+    }
+
+    #[must_use]
+    pub fn samples(mut self, samples: Vec<Vec<String>>) -> Self {
+        self.schema.samples = Some(samples);
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> Schema {
+        self.schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BuiltinSchema, REGISTRY, Schema, SchemaBuilder, SchemaError};
+    use std::collections::HashMap;
+
+    #[test]
+    fn builtin_schema_stays_in_sync_with_bundled_files() {
+        assert_eq!(BuiltinSchema::ALL.len(), REGISTRY.len());
+        for schema in BuiltinSchema::ALL {
+            assert!(Schema::try_for_name(schema.name()).is_ok());
+        }
+    }
+
+    fn test_schema() -> Schema {
+        SchemaBuilder::new()
+            .name("test")
+            .mapping(HashMap::from([("б".to_owned(), "b".to_owned())]))
+            .build()
+    }
+
+    #[test]
+    fn schema_builder_builds_a_working_schema() {
+        let schema = test_schema();
+        assert_eq!(schema.name(), "test");
+        assert_eq!(crate::parse_by_schema("б", &schema), "b");
+    }
+
+    #[test]
+    fn from_json_str_parses_a_valid_schema() {
+        let schema =
+            Schema::from_json_str(r#"{"name":"test","description":"d","url":"u","mapping":{"б":"b"}}"#)
+                .unwrap();
+        assert_eq!(schema.name(), "test");
+        assert_eq!(crate::parse_by_schema("б", &schema), "b");
+    }
+
+    #[test]
+    fn from_json_str_reports_a_parse_error() {
+        assert!(matches!(
+            Schema::from_json_str("not json"),
+            Err(SchemaError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn from_reader_reads_a_schema_to_completion() {
+        let json = r#"{"name":"test","description":"d","url":"u","mapping":{"б":"b"}}"#;
+        let schema = Schema::from_reader(json.as_bytes()).unwrap();
+        assert_eq!(schema.name(), "test");
+    }
+
+    #[test]
+    fn self_test_passes_on_a_matching_sample() {
+        let schema = SchemaBuilder::new()
+            .mapping(HashMap::from([("б".to_owned(), "b".to_owned())]))
+            .samples(vec![vec!["б".to_owned(), "b".to_owned()]])
+            .build();
+        assert!(schema.self_test().is_ok());
+    }
+
+    #[test]
+    fn self_test_catches_a_sample_mismatch() {
+        let schema = SchemaBuilder::new()
+            .mapping(HashMap::from([("б".to_owned(), "b".to_owned())]))
+            .samples(vec![vec!["б".to_owned(), "z".to_owned()]])
+            .build();
+        assert!(matches!(
+            schema.self_test(),
+            Err(SchemaError::SampleMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{Schema, parse_by_schema};
+
+/// Options controlling how [`slugify`] turns transliterated text into a
+/// URL-safe slug.
+#[derive(Debug, Clone)]
+pub struct SlugOptions {
+    /// Character used to join/collapse runs of non `[a-z0-9]` output. Common
+    /// choices are `-` or `_`.
+    pub separator: char,
+    /// Downcase the transliterated text before building the slug.
+    pub lowercase: bool,
+    /// Replacement for characters the schema left untransliterated (e.g. a
+    /// Cyrillic letter with no mapping). When `None`, such characters are
+    /// treated like any other separator-triggering character.
+    pub fallback: Option<char>,
+    /// Truncate the slug to at most this many chars, cutting at the last
+    /// separator within that range; when there is none, the slug is cut
+    /// mid-word at exactly this many chars.
+    pub max_len: Option<usize>,
+}
+
+impl Default for SlugOptions {
+    fn default() -> Self {
+        SlugOptions {
+            separator: '-',
+            lowercase: true,
+            fallback: None,
+            max_len: None,
+        }
+    }
+}
+
+impl SlugOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    #[must_use]
+    pub fn with_lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+
+    #[must_use]
+    pub fn with_fallback(mut self, fallback: Option<char>) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_len(mut self, max_len: Option<usize>) -> Self {
+        self.max_len = max_len;
+        self
+    }
+}
+
+/// Transliterate `s` with `schema`, then normalize it into a URL-safe slug:
+/// downcase (when `options.lowercase` is set), collapse any run of chars
+/// other than ASCII letters and digits into a single `options.separator`
+/// (non-ASCII chars become `options.fallback` instead when it is set), trim
+/// separators from both ends, and truncate to `options.max_len` if given.
+///
+/// ```
+/// let schema = iuliia_rust::Schema::for_name("wikipedia");
+/// let options = iuliia_rust::SlugOptions::new();
+/// assert_eq!(iuliia_rust::slugify("Привет, мир!", schema, &options), "privet-mir");
+/// ```
+#[must_use]
+pub fn slugify(s: &str, schema: &Schema, options: &SlugOptions) -> String {
+    let transliterated = parse_by_schema(s, schema);
+
+    let mut normalized = String::with_capacity(transliterated.len());
+    for ch in transliterated.chars() {
+        if ch.is_ascii_alphanumeric() {
+            normalized.push(if options.lowercase {
+                ch.to_ascii_lowercase()
+            } else {
+                ch
+            });
+        } else if let Some(fallback) = (!ch.is_ascii()).then_some(options.fallback).flatten() {
+            normalized.push(fallback);
+        } else {
+            normalized.push(options.separator);
+        }
+    }
+
+    let collapsed = collapse_separators(&normalized, options.separator);
+    match options.max_len {
+        Some(max_len) => truncate_on_boundary(&collapsed, max_len, options.separator),
+        None => collapsed,
+    }
+}
+
+fn collapse_separators(s: &str, separator: char) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut last_was_separator = true; // trims a leading separator for free
+    for ch in s.chars() {
+        if ch == separator {
+            if !last_was_separator {
+                result.push(separator);
+            }
+            last_was_separator = true;
+        } else {
+            result.push(ch);
+            last_was_separator = false;
+        }
+    }
+    while result.ends_with(separator) {
+        result.pop();
+    }
+    result
+}
+
+fn truncate_on_boundary(s: &str, max_len: usize, separator: char) -> String {
+    if s.chars().count() <= max_len {
+        return s.to_owned();
+    }
+    let truncated: String = s.chars().take(max_len).collect();
+    match truncated.rfind(separator) {
+        Some(boundary) => truncated[..boundary].to_owned(),
+        None => truncated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SlugOptions, slugify};
+    use crate::{Schema, SchemaBuilder};
+
+    // An empty-mapping schema passes every char through unchanged, so these
+    // tests can exercise `slugify`'s own logic without depending on any
+    // particular schema's transliteration rules.
+    fn passthrough_schema() -> Schema {
+        SchemaBuilder::new().build()
+    }
+
+    #[test]
+    fn fallback_replaces_untransliterated_chars() {
+        let schema = passthrough_schema();
+        let options = SlugOptions::new().with_fallback(Some('x'));
+        assert_eq!(slugify("я world", &schema, &options), "x-world");
+    }
+
+    #[test]
+    fn max_len_truncates_on_a_separator_boundary() {
+        let schema = passthrough_schema();
+        let options = SlugOptions::new().with_max_len(Some(8));
+        assert_eq!(slugify("foo bar baz", &schema, &options), "foo-bar");
+    }
+
+    #[test]
+    fn max_len_hard_truncates_when_no_boundary_is_in_range() {
+        let schema = passthrough_schema();
+        let options = SlugOptions::new().with_max_len(Some(2));
+        assert_eq!(slugify("foobar", &schema, &options), "fo");
+    }
+
+    #[test]
+    fn all_separator_input_collapses_to_empty() {
+        let schema = passthrough_schema();
+        let options = SlugOptions::new();
+        assert_eq!(slugify("!!!", &schema, &options), "");
+    }
+}